@@ -25,10 +25,14 @@ impl fmt::Display for TokenKind {
         use self::TokenKind::*;
         match self {
             Number(n) => n.fmt(f),
+            Ident(name) => write!(f, "{}", name),
             Plus => write!(f, "+"),
             Minus => write!(f, "-"),
             Asterisk => write!(f, "*"),
             Slash => write!(f, "/"),
+            Caret => write!(f, "^"),
+            Equal => write!(f, "="),
+            Comma => write!(f, ","),
             LParen => write!(f, "("),
             RParen => write!(f, ")"),
         }
@@ -37,7 +41,11 @@ impl fmt::Display for TokenKind {
 
 impl fmt::Display for Loc {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}-{}", self.0, self.1)
+        write!(
+            f,
+            "{}:{}-{}:{}",
+            self.start.line, self.start.col, self.end.line, self.end.col
+        )
     }
 }
 
@@ -47,7 +55,7 @@ impl fmt::Display for LexError {
         let loc = &self.loc;
         match self.value {
             InvalidChar(c) => write!(f, "{}: invalid char '{}'", loc, c),
-            Eof => write!(f, "End of file"),
+            NumberOverflow => write!(f, "{}: number literal is too large", loc),
         }
     }
 }
@@ -69,7 +77,13 @@ impl fmt::Display for ParseError {
                 "{}: expression after '{}' is redundant",
                 tok.loc, tok.value
             ),
-            Eof => write!(f, "End of file"),
+            ExpectedIdent(tok) => write!(
+                f,
+                "{}: expected an identifier before '{}'",
+                tok.loc, tok.value
+            ),
+            ExpectedEquals(tok) => write!(f, "{}: expected '=' after '{}'", tok.loc, tok.value),
+            Eof(loc) => write!(f, "{}: unexpected end of file", loc),
         }
     }
 }
@@ -93,31 +107,73 @@ impl StdError for Error {
     }
 }
 
-fn print_annot(input: &str, loc: Loc) {
-    eprintln!("{}", input);
-    eprintln!("{}{}", " ".repeat(loc.0), "^".repeat(loc.1 - loc.0));
+/// Computes the `(start_col, caret_count)` of the `^` run for `line_no`
+/// within `line`, given that `loc` spans `line_no`. Split out of
+/// `print_annot` so the column math can be tested without capturing stderr.
+fn caret_span(line: &str, line_no: usize, loc: &Loc) -> (usize, usize) {
+    let start_col = if line_no == loc.start.line {
+        loc.start.col
+    } else {
+        1
+    };
+    let end_col = if line_no == loc.end.line {
+        loc.end.col
+    } else {
+        line.len() + 1
+    };
+    let carets = end_col.saturating_sub(start_col).max(1);
+    (start_col, carets)
+}
+
+/// Prints the source line(s) spanned by `loc` and places a run of `^`
+/// under the columns it covers. `loc` may span multiple lines; each line
+/// in the span is printed in full with its own caret run.
+fn print_annot(input: &str, loc: &Loc) {
+    let lines: Vec<&str> = input.split('\n').collect();
+    for line_no in loc.start.line..=loc.end.line {
+        let Some(line) = lines.get(line_no - 1) else {
+            continue;
+        };
+        eprintln!("{}", line);
+
+        let (start_col, carets) = caret_span(line, line_no, loc);
+        eprintln!("{}{}", " ".repeat(start_col - 1), "^".repeat(carets));
+    }
 }
 
 impl Error {
     pub fn show_diagnostic(&self, input: &str) {
         use self::Error::*;
         use self::ParseError as P;
-        let (e, loc): (&StdError, Loc) = match self {
-            Lexer(e) => (e, e.loc.clone()),
+        let (e, loc): (&dyn StdError, Option<Loc>) = match self {
+            Lexer(e) => (e, Some(e.loc.clone())),
             Parser(e) => {
                 let loc = match e {
                     P::UnexpectedToken(Token { loc, .. })
                     | P::NotExpression(Token { loc, .. })
                     | P::NotOperator(Token { loc, .. })
-                    | P::UnclosedOpenParen(Token { loc, .. }) => loc.clone(),
-                    P::RedundantExpression(Token { loc, .. }) => Loc(loc.0, input.len()),
-                    P::Eof => Loc(input.len(), input.len() + 1),
+                    | P::UnclosedOpenParen(Token { loc, .. })
+                    | P::RedundantExpression(Token { loc, .. })
+                    | P::ExpectedIdent(Token { loc, .. })
+                    | P::ExpectedEquals(Token { loc, .. }) => Some(loc.clone()),
+                    P::Eof(loc) => Some(loc.clone()),
                 };
                 (e, loc)
             }
         };
         eprintln!("{}", e);
-        print_annot(input, loc);
+        if let Some(loc) = loc {
+            print_annot(input, &loc);
+        }
+    }
+}
+
+/// Renders every error from a batch parse (e.g. [`crate::parser::parse_all`])
+/// in one pass, each with its own annotated caret line, instead of stopping
+/// at the first diagnostic.
+pub fn show_diagnostics(errors: &[Error], input: &str) {
+    for e in errors {
+        e.show_diagnostic(input);
     }
 }
 
@@ -133,6 +189,13 @@ pub fn show_trace<E: StdError>(e: E) {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum InterpreterErrorKind {
     DivisionByZero,
+    UndefinedVariable(String),
+    UnknownFunction(String),
+    ArityMismatch {
+        name: String,
+        expected: usize,
+        got: usize,
+    },
 }
 
 pub type InterpreterError = Annot<InterpreterErrorKind>;
@@ -140,8 +203,19 @@ pub type InterpreterError = Annot<InterpreterErrorKind>;
 impl fmt::Display for InterpreterError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::InterpreterErrorKind::*;
-        match self.value {
+        match &self.value {
             DivisionByZero => write!(f, "division by zero"),
+            UndefinedVariable(name) => write!(f, "undefined variable '{}'", name),
+            UnknownFunction(name) => write!(f, "unknown function '{}'", name),
+            ArityMismatch {
+                name,
+                expected,
+                got,
+            } => write!(
+                f,
+                "'{}' expects {} argument(s), got {}",
+                name, expected, got
+            ),
         }
     }
 }
@@ -149,17 +223,59 @@ impl fmt::Display for InterpreterError {
 impl StdError for InterpreterError {
     fn description(&self) -> &str {
         use self::InterpreterErrorKind::*;
-        match self.value {
+        match &self.value {
             DivisionByZero => "the right hand expression of the division evaluates to zero",
+            UndefinedVariable(_) => "the variable has not been assigned a value",
+            UnknownFunction(_) => "no built-in function with this name exists",
+            ArityMismatch { .. } => "the function was called with the wrong number of arguments",
         }
     }
 }
 
 impl InterpreterError {
     pub fn show_diagnostic(&self, input: &str) {
-        // エラー情報を簡単に表示し
         eprintln!("{}", self);
-        // エラー位置を指示する
-        print_annot(input, self.loc.clone());
+        print_annot(input, &self.loc);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    #[test]
+    fn loc_tracks_line_and_col_across_newlines() {
+        let (tokens, eof_loc) = lex("1\n22\n333").unwrap();
+        assert_eq!(tokens[0].loc.start.line, 1);
+        assert_eq!(tokens[0].loc.start.col, 1);
+        assert_eq!(tokens[1].loc.start.line, 2);
+        assert_eq!(tokens[1].loc.start.col, 1);
+        assert_eq!(tokens[2].loc.start.line, 3);
+        assert_eq!(tokens[2].loc.start.col, 1);
+        assert_eq!(eof_loc.start.line, 3);
+        assert_eq!(eof_loc.start.col, 4);
+    }
+
+    #[test]
+    fn caret_span_covers_each_line_of_a_multi_line_loc() {
+        // `1\n/0` lexes to three tokens (`1`, `/`, `0`) whose merged span
+        // starts on line 1 col 1 and ends on line 2 col 2 (just past the
+        // `0`), mirroring the span a `DivisionByZero` error would carry.
+        let input = "1\n/0";
+        let (tokens, _) = lex(input).unwrap();
+        let loc = tokens[0].loc.merge(&tokens[2].loc);
+        assert_eq!(loc.start.line, 1);
+        assert_eq!(loc.end.line, 2);
+
+        let lines: Vec<&str> = input.split('\n').collect();
+        assert_eq!(caret_span(lines[0], 1, &loc), (1, 1));
+        assert_eq!(caret_span(lines[1], 2, &loc), (1, 2));
+    }
+
+    #[test]
+    fn number_overflow_is_a_lex_error() {
+        let err = lex("99999999999999999999999").unwrap_err();
+        assert_eq!(err.value, crate::lexer::LexErrorKind::NumberOverflow);
     }
 }
@@ -0,0 +1,172 @@
+use crate::error::{InterpreterError, InterpreterErrorKind};
+use crate::lexer::Loc;
+use crate::parser::{Ast, AstKind, BinOpKind, Statement, StatementKind, UniOpKind};
+use std::collections::HashMap;
+
+type BuiltinFn = fn(&[f64]) -> f64;
+
+/// Built-in functions callable from expressions, keyed by name with their
+/// expected arity. Looked up by [`call_builtin`] on every `AstKind::Call`.
+const BUILTINS: &[(&str, usize, BuiltinFn)] = &[
+    ("sqrt", 1, |args| args[0].sqrt()),
+    ("max", 2, |args| args[0].max(args[1])),
+    ("min", 2, |args| args[0].min(args[1])),
+];
+
+fn call_builtin(name: &str, args: &[f64], loc: &Loc) -> Result<f64, InterpreterError> {
+    let (_, arity, f) = BUILTINS
+        .iter()
+        .find(|(builtin_name, _, _)| *builtin_name == name)
+        .ok_or_else(|| InterpreterError {
+            value: InterpreterErrorKind::UnknownFunction(name.to_string()),
+            loc: loc.clone(),
+        })?;
+    if args.len() != *arity {
+        return Err(InterpreterError {
+            value: InterpreterErrorKind::ArityMismatch {
+                name: name.to_string(),
+                expected: *arity,
+                got: args.len(),
+            },
+            loc: loc.clone(),
+        });
+    }
+    Ok(f(args))
+}
+
+/// Evaluates statements while holding the variable bindings they accumulate
+/// across a REPL session.
+pub struct Interpreter {
+    env: HashMap<String, f64>,
+}
+
+impl Interpreter {
+    pub fn new() -> Self {
+        Self {
+            env: HashMap::new(),
+        }
+    }
+
+    pub fn eval_statement(&mut self, statement: &Statement) -> Result<f64, InterpreterError> {
+        match &statement.value {
+            StatementKind::Expr(e) => self.eval(e),
+            StatementKind::Assign { name, expr } => {
+                let value = self.eval(expr)?;
+                self.env.insert(name.clone(), value);
+                Ok(value)
+            }
+        }
+    }
+
+    fn eval(&self, expr: &Ast) -> Result<f64, InterpreterError> {
+        match &expr.value {
+            AstKind::Num(n) => Ok(*n as f64),
+            AstKind::Ident(name) => self.env.get(name).copied().ok_or_else(|| InterpreterError {
+                value: InterpreterErrorKind::UndefinedVariable(name.clone()),
+                loc: expr.loc.clone(),
+            }),
+            AstKind::UniOp { op, e } => {
+                let e = self.eval(e)?;
+                match op.value {
+                    UniOpKind::Plus => Ok(e),
+                    UniOpKind::Minus => Ok(-e),
+                }
+            }
+            AstKind::Call { name, args } => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                call_builtin(name, &args, &expr.loc)
+            }
+            AstKind::BinOp { op, l, r } => {
+                let l = self.eval(l)?;
+                let r = self.eval(r)?;
+                match op.value {
+                    BinOpKind::Add => Ok(l + r),
+                    BinOpKind::Sub => Ok(l - r),
+                    BinOpKind::Mult => Ok(l * r),
+                    BinOpKind::Pow => Ok(l.powf(r)),
+                    BinOpKind::Div => {
+                        if r == 0.0 {
+                            Err(InterpreterError {
+                                value: InterpreterErrorKind::DivisionByZero,
+                                loc: expr.loc.clone(),
+                            })
+                        } else {
+                            Ok(l / r)
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+    use crate::parser::parse_all;
+
+    fn eval_all(interpreter: &mut Interpreter, input: &str) -> Vec<f64> {
+        let (tokens, eof_loc) = lex(input).unwrap();
+        let statements = parse_all(tokens, eof_loc).unwrap();
+        statements
+            .iter()
+            .map(|s| interpreter.eval_statement(s).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn assignment_persists_across_statements() {
+        let mut interpreter = Interpreter::new();
+        assert_eq!(eval_all(&mut interpreter, "x = 2"), vec![2.0]);
+        assert_eq!(eval_all(&mut interpreter, "x + 3"), vec![5.0]);
+    }
+
+    #[test]
+    fn undefined_variable_is_an_error() {
+        let mut interpreter = Interpreter::new();
+        let (tokens, eof_loc) = lex("y").unwrap();
+        let statement = &parse_all(tokens, eof_loc).unwrap()[0];
+        assert_eq!(
+            interpreter.eval_statement(statement).unwrap_err().value,
+            InterpreterErrorKind::UndefinedVariable("y".to_string())
+        );
+    }
+
+    #[test]
+    fn builtin_call_dispatch() {
+        let mut interpreter = Interpreter::new();
+        assert_eq!(eval_all(&mut interpreter, "sqrt(9)"), vec![3.0]);
+        assert_eq!(eval_all(&mut interpreter, "max(1, 2)"), vec![2.0]);
+        assert_eq!(eval_all(&mut interpreter, "min(1, 2)"), vec![1.0]);
+    }
+
+    #[test]
+    fn unknown_function_is_an_error() {
+        let mut interpreter = Interpreter::new();
+        let (tokens, eof_loc) = lex("nope(1)").unwrap();
+        let statement = &parse_all(tokens, eof_loc).unwrap()[0];
+        assert_eq!(
+            interpreter.eval_statement(statement).unwrap_err().value,
+            InterpreterErrorKind::UnknownFunction("nope".to_string())
+        );
+    }
+
+    #[test]
+    fn builtin_arity_mismatch_is_an_error() {
+        let mut interpreter = Interpreter::new();
+        let (tokens, eof_loc) = lex("sqrt(1, 2)").unwrap();
+        let statement = &parse_all(tokens, eof_loc).unwrap()[0];
+        assert_eq!(
+            interpreter.eval_statement(statement).unwrap_err().value,
+            InterpreterErrorKind::ArityMismatch {
+                name: "sqrt".to_string(),
+                expected: 1,
+                got: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,260 @@
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Position {
+    pub byte_offset: usize,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl Position {
+    fn new(byte_offset: usize, line: usize, col: usize) -> Self {
+        Self {
+            byte_offset,
+            line,
+            col,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Loc {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Loc {
+    fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    pub(crate) fn merge(&self, other: &Loc) -> Loc {
+        let start = if self.start.byte_offset <= other.start.byte_offset {
+            self.start.clone()
+        } else {
+            other.start.clone()
+        };
+        let end = if self.end.byte_offset >= other.end.byte_offset {
+            self.end.clone()
+        } else {
+            other.end.clone()
+        };
+        Loc::new(start, end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Annot<T> {
+    pub value: T,
+    pub loc: Loc,
+}
+
+impl<T> Annot<T> {
+    pub(crate) fn new(value: T, loc: Loc) -> Self {
+        Self { value, loc }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    Number(u64),
+    Ident(String),
+    Plus,
+    Minus,
+    Asterisk,
+    Slash,
+    Caret,
+    Equal,
+    Comma,
+    LParen,
+    RParen,
+}
+
+pub type Token = Annot<TokenKind>;
+
+impl Token {
+    fn number(n: u64, loc: Loc) -> Self {
+        Self::new(TokenKind::Number(n), loc)
+    }
+    fn ident(name: String, loc: Loc) -> Self {
+        Self::new(TokenKind::Ident(name), loc)
+    }
+    fn plus(loc: Loc) -> Self {
+        Self::new(TokenKind::Plus, loc)
+    }
+    fn minus(loc: Loc) -> Self {
+        Self::new(TokenKind::Minus, loc)
+    }
+    fn asterisk(loc: Loc) -> Self {
+        Self::new(TokenKind::Asterisk, loc)
+    }
+    fn slash(loc: Loc) -> Self {
+        Self::new(TokenKind::Slash, loc)
+    }
+    fn caret(loc: Loc) -> Self {
+        Self::new(TokenKind::Caret, loc)
+    }
+    fn equal(loc: Loc) -> Self {
+        Self::new(TokenKind::Equal, loc)
+    }
+    fn comma(loc: Loc) -> Self {
+        Self::new(TokenKind::Comma, loc)
+    }
+    fn lparen(loc: Loc) -> Self {
+        Self::new(TokenKind::LParen, loc)
+    }
+    fn rparen(loc: Loc) -> Self {
+        Self::new(TokenKind::RParen, loc)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LexErrorKind {
+    InvalidChar(char),
+    NumberOverflow,
+}
+
+pub type LexError = Annot<LexErrorKind>;
+
+impl LexError {
+    fn invalid_char(c: char, loc: Loc) -> Self {
+        LexError::new(LexErrorKind::InvalidChar(c), loc)
+    }
+    fn number_overflow(loc: Loc) -> Self {
+        LexError::new(LexErrorKind::NumberOverflow, loc)
+    }
+}
+
+/// Tracks the current scan position as byte offset, line and column while
+/// the lexer walks the input, so every emitted `Loc` can point back at the
+/// exact source line.
+struct Cursor {
+    pos: usize,
+    line: usize,
+    col: usize,
+}
+
+impl Cursor {
+    fn new() -> Self {
+        Self {
+            pos: 0,
+            line: 1,
+            col: 1,
+        }
+    }
+
+    fn position(&self) -> Position {
+        Position::new(self.pos, self.line, self.col)
+    }
+
+    fn advance(&mut self, byte: u8) {
+        self.pos += 1;
+        if byte == b'\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+    }
+}
+
+/// Lexes `input` into a token stream plus the `Loc` of the position right
+/// after the last character, so a parser that runs out of tokens mid-way
+/// through an expression can still point an annotated caret at end-of-input
+/// instead of reporting a bare "End of file" with nothing to point at.
+pub fn lex(input: &str) -> Result<(Vec<Token>, Loc), LexError> {
+    let bytes = input.as_bytes();
+    let mut tokens = Vec::new();
+    let mut cursor = Cursor::new();
+
+    while cursor.pos < bytes.len() {
+        let b = bytes[cursor.pos];
+        match b {
+            b'+' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::plus(Loc::new(start, cursor.position())));
+            }
+            b'-' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::minus(Loc::new(start, cursor.position())));
+            }
+            b'*' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::asterisk(Loc::new(start, cursor.position())));
+            }
+            b'/' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::slash(Loc::new(start, cursor.position())));
+            }
+            b'(' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::lparen(Loc::new(start, cursor.position())));
+            }
+            b')' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::rparen(Loc::new(start, cursor.position())));
+            }
+            b'=' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::equal(Loc::new(start, cursor.position())));
+            }
+            b',' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::comma(Loc::new(start, cursor.position())));
+            }
+            b'^' => {
+                let start = cursor.position();
+                cursor.advance(b);
+                tokens.push(Token::caret(Loc::new(start, cursor.position())));
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                let start = cursor.position();
+                let begin = cursor.pos;
+                while cursor.pos < bytes.len()
+                    && (bytes[cursor.pos].is_ascii_alphanumeric() || bytes[cursor.pos] == b'_')
+                {
+                    cursor.advance(bytes[cursor.pos]);
+                }
+                let name = std::str::from_utf8(&bytes[begin..cursor.pos])
+                    .expect("identifier bytes are ASCII")
+                    .to_string();
+                tokens.push(Token::ident(name, Loc::new(start, cursor.position())));
+            }
+            b'0'..=b'9' => {
+                let start = cursor.position();
+                let mut n = 0u64;
+                while cursor.pos < bytes.len() && bytes[cursor.pos].is_ascii_digit() {
+                    let digit = u64::from(bytes[cursor.pos] - b'0');
+                    n = n
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add(digit))
+                        .ok_or_else(|| {
+                            LexError::number_overflow(Loc::new(start.clone(), cursor.position()))
+                        })?;
+                    cursor.advance(bytes[cursor.pos]);
+                }
+                tokens.push(Token::number(n, Loc::new(start, cursor.position())));
+            }
+            b' ' | b'\t' | b'\n' => {
+                cursor.advance(b);
+            }
+            b => {
+                let start = cursor.position();
+                cursor.advance(b);
+                return Err(LexError::invalid_char(
+                    b as char,
+                    Loc::new(start, cursor.position()),
+                ));
+            }
+        }
+    }
+
+    let eof_loc = Loc::new(cursor.position(), cursor.position());
+    Ok((tokens, eof_loc))
+}
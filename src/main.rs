@@ -1,4 +1,10 @@
+mod error;
+mod interpreter;
 mod lexer;
+mod parser;
+
+use error::{show_diagnostics, show_trace, Error};
+use interpreter::Interpreter;
 use std::io;
 
 fn prompt(s: &str) -> io::Result<()> {
@@ -17,12 +23,36 @@ fn main() {
     let stdin = stdin.lock();
     let stdin = BufReader::new(stdin);
     let mut lines = stdin.lines();
+    let mut interpreter = Interpreter::new();
 
     loop {
         prompt("> ").unwrap();
         if let Some(Ok(line)) = lines.next() {
-            let token = lexer::lex(&line);
-            println!("{:?}", token);
+            let (tokens, eof_loc) = match lexer::lex(&line) {
+                Ok(result) => result,
+                Err(e) => {
+                    let e = Error::from(e);
+                    e.show_diagnostic(&line);
+                    show_trace(e);
+                    continue;
+                }
+            };
+            let statements = match parser::parse_all(tokens, eof_loc) {
+                Ok(statements) => statements,
+                Err(errors) => {
+                    show_diagnostics(&errors, &line);
+                    for e in errors {
+                        show_trace(e);
+                    }
+                    continue;
+                }
+            };
+            for statement in &statements {
+                match interpreter.eval_statement(statement) {
+                    Ok(value) => println!("{}", value),
+                    Err(e) => e.show_diagnostic(&line),
+                }
+            }
         } else {
             break;
         }
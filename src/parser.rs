@@ -0,0 +1,520 @@
+use crate::error::Error;
+use crate::lexer::{Annot, Loc, Token, TokenKind};
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::mem::discriminant;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AstKind {
+    Num(u64),
+    Ident(String),
+    UniOp { op: UniOp, e: Box<Ast> },
+    BinOp { op: BinOp, l: Box<Ast>, r: Box<Ast> },
+    Call { name: String, args: Vec<Ast> },
+}
+
+pub type Ast = Annot<AstKind>;
+
+impl Ast {
+    fn num(n: u64, loc: Loc) -> Self {
+        Self::new(AstKind::Num(n), loc)
+    }
+    fn ident(name: String, loc: Loc) -> Self {
+        Self::new(AstKind::Ident(name), loc)
+    }
+    fn uniop(op: UniOp, e: Ast, loc: Loc) -> Self {
+        Self::new(AstKind::UniOp { op, e: Box::new(e) }, loc)
+    }
+    fn binop(op: BinOp, l: Ast, r: Ast, loc: Loc) -> Self {
+        Self::new(
+            AstKind::BinOp {
+                op,
+                l: Box::new(l),
+                r: Box::new(r),
+            },
+            loc,
+        )
+    }
+    fn call(name: String, args: Vec<Ast>, loc: Loc) -> Self {
+        Self::new(AstKind::Call { name, args }, loc)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StatementKind {
+    Expr(Ast),
+    Assign { name: String, expr: Ast },
+}
+
+pub type Statement = Annot<StatementKind>;
+
+impl Statement {
+    fn expr(e: Ast) -> Self {
+        let loc = e.loc.clone();
+        Self::new(StatementKind::Expr(e), loc)
+    }
+    fn assign(name: String, expr: Ast, loc: Loc) -> Self {
+        Self::new(StatementKind::Assign { name, expr }, loc)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UniOpKind {
+    Plus,
+    Minus,
+}
+
+pub type UniOp = Annot<UniOpKind>;
+
+impl UniOp {
+    fn plus(loc: Loc) -> Self {
+        Self::new(UniOpKind::Plus, loc)
+    }
+    fn minus(loc: Loc) -> Self {
+        Self::new(UniOpKind::Minus, loc)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BinOpKind {
+    Add,
+    Sub,
+    Mult,
+    Div,
+    Pow,
+}
+
+pub type BinOp = Annot<BinOpKind>;
+
+impl BinOp {
+    fn add(loc: Loc) -> Self {
+        Self::new(BinOpKind::Add, loc)
+    }
+    fn sub(loc: Loc) -> Self {
+        Self::new(BinOpKind::Sub, loc)
+    }
+    fn mult(loc: Loc) -> Self {
+        Self::new(BinOpKind::Mult, loc)
+    }
+    fn div(loc: Loc) -> Self {
+        Self::new(BinOpKind::Div, loc)
+    }
+    fn pow(loc: Loc) -> Self {
+        Self::new(BinOpKind::Pow, loc)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    UnexpectedToken(Token),
+    NotExpression(Token),
+    NotOperator(Token),
+    UnclosedOpenParen(Token),
+    RedundantExpression(Token),
+    ExpectedIdent(Token),
+    ExpectedEquals(Token),
+    Eof(Loc),
+}
+
+/// Binding power of an infix operator, lowest to highest. `Prefix` is the
+/// power a unary `+`/`-` parses its operand at, so unary binds tighter
+/// than any binary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Lowest,
+    Sum,
+    Product,
+    Power,
+    Prefix,
+}
+
+impl Precedence {
+    /// The next precedence down, used to make an infix operator
+    /// right-associative: parsing its right-hand side at one level below
+    /// its own lets an operator of the same precedence bind there too.
+    fn lower(self) -> Self {
+        match self {
+            Precedence::Prefix => Precedence::Power,
+            Precedence::Power => Precedence::Product,
+            Precedence::Product => Precedence::Sum,
+            Precedence::Sum => Precedence::Lowest,
+            Precedence::Lowest => Precedence::Lowest,
+        }
+    }
+}
+
+type Tokens = Peekable<std::vec::IntoIter<Token>>;
+type PrefixParseFn = fn(&mut Parser, Token) -> Result<Ast, ParseError>;
+type InfixParseFn = fn(&mut Parser, Ast, Token) -> Result<Ast, ParseError>;
+
+/// A Pratt (precedence-climbing) parser. `prefix_fns`/`infix_fns` are
+/// registered once per [`TokenKind`] discriminant in [`Parser::new`];
+/// [`Parser::parse_expr`] looks a token up in whichever table applies and
+/// lets the matching function decide how to consume it. This mirrors the
+/// Monkey-style `prefix_parse_fns`/`infix_parse_fns` registration, adapted
+/// to a `TokenKind` that carries payloads (hence keying on
+/// `mem::discriminant` rather than the token itself).
+struct Parser {
+    tokens: Tokens,
+    eof_loc: Loc,
+    prefix_fns: HashMap<std::mem::Discriminant<TokenKind>, PrefixParseFn>,
+    infix_fns: HashMap<std::mem::Discriminant<TokenKind>, (Precedence, InfixParseFn)>,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>, eof_loc: Loc) -> Self {
+        let mut parser = Self {
+            tokens: tokens.into_iter().peekable(),
+            eof_loc,
+            prefix_fns: HashMap::new(),
+            infix_fns: HashMap::new(),
+        };
+
+        parser.register_prefix(TokenKind::Number(0), parse_number);
+        parser.register_prefix(TokenKind::Ident(String::new()), parse_ident);
+        parser.register_prefix(TokenKind::Plus, parse_unary);
+        parser.register_prefix(TokenKind::Minus, parse_unary);
+        parser.register_prefix(TokenKind::LParen, parse_group);
+
+        parser.register_infix(TokenKind::Plus, Precedence::Sum, parse_binop);
+        parser.register_infix(TokenKind::Minus, Precedence::Sum, parse_binop);
+        parser.register_infix(TokenKind::Asterisk, Precedence::Product, parse_binop);
+        parser.register_infix(TokenKind::Slash, Precedence::Product, parse_binop);
+        parser.register_infix(TokenKind::Caret, Precedence::Power, parse_binop);
+
+        parser
+    }
+
+    fn register_prefix(&mut self, kind: TokenKind, f: PrefixParseFn) {
+        self.prefix_fns.insert(discriminant(&kind), f);
+    }
+
+    fn register_infix(&mut self, kind: TokenKind, prec: Precedence, f: InfixParseFn) {
+        self.infix_fns.insert(discriminant(&kind), (prec, f));
+    }
+
+    fn peek_precedence(&mut self) -> Precedence {
+        let discr = match self.tokens.peek() {
+            Some(tok) => discriminant(&tok.value),
+            None => return Precedence::Lowest,
+        };
+        self.infix_fns
+            .get(&discr)
+            .map(|(prec, _)| *prec)
+            .unwrap_or(Precedence::Lowest)
+    }
+
+    fn parse_expr(&mut self, min_prec: Precedence) -> Result<Ast, ParseError> {
+        let eof_loc = self.eof_loc.clone();
+        let tok = self.tokens.next().ok_or(ParseError::Eof(eof_loc))?;
+        let prefix = *self
+            .prefix_fns
+            .get(&discriminant(&tok.value))
+            .ok_or_else(|| ParseError::NotExpression(tok.clone()))?;
+        let mut left = prefix(self, tok)?;
+
+        while min_prec < self.peek_precedence() {
+            let op_tok = self.tokens.next().unwrap();
+            let (_, infix) = *self
+                .infix_fns
+                .get(&discriminant(&op_tok.value))
+                .ok_or_else(|| ParseError::NotOperator(op_tok.clone()))?;
+            left = infix(self, left, op_tok)?;
+        }
+        Ok(left)
+    }
+}
+
+fn parse_number(_parser: &mut Parser, tok: Token) -> Result<Ast, ParseError> {
+    match tok.value {
+        TokenKind::Number(n) => Ok(Ast::num(n, tok.loc)),
+        _ => unreachable!(),
+    }
+}
+
+fn parse_ident(parser: &mut Parser, tok: Token) -> Result<Ast, ParseError> {
+    let name = match tok.value {
+        TokenKind::Ident(name) => name,
+        _ => unreachable!(),
+    };
+    match parser.tokens.peek() {
+        Some(Token {
+            value: TokenKind::LParen,
+            ..
+        }) => {
+            let open = parser.tokens.next().unwrap();
+            parse_call(parser, name, tok.loc, open)
+        }
+        _ => Ok(Ast::ident(name, tok.loc)),
+    }
+}
+
+/// Parses the `(arg, arg, ...)` tail of a call expression, `open` being the
+/// already-consumed `(`. An empty argument list (`f()`) is allowed.
+fn parse_call(
+    parser: &mut Parser,
+    name: String,
+    name_loc: Loc,
+    open: Token,
+) -> Result<Ast, ParseError> {
+    let mut args = Vec::new();
+    if !matches!(
+        parser.tokens.peek(),
+        Some(Token {
+            value: TokenKind::RParen,
+            ..
+        })
+    ) {
+        loop {
+            args.push(parser.parse_expr(Precedence::Lowest)?);
+            match parser.tokens.peek() {
+                Some(Token {
+                    value: TokenKind::Comma,
+                    ..
+                }) => {
+                    parser.tokens.next();
+                }
+                _ => break,
+            }
+        }
+    }
+    match parser.tokens.next() {
+        Some(
+            tok @ Token {
+                value: TokenKind::RParen,
+                ..
+            },
+        ) => {
+            let loc = name_loc.merge(&tok.loc);
+            Ok(Ast::call(name, args, loc))
+        }
+        Some(t) => Err(ParseError::UnexpectedToken(t)),
+        None => Err(ParseError::UnclosedOpenParen(open)),
+    }
+}
+
+fn parse_unary(parser: &mut Parser, tok: Token) -> Result<Ast, ParseError> {
+    let op = match tok.value {
+        TokenKind::Plus => UniOp::plus(tok.loc.clone()),
+        TokenKind::Minus => UniOp::minus(tok.loc.clone()),
+        _ => unreachable!(),
+    };
+    let e = parser.parse_expr(Precedence::Prefix)?;
+    let loc = tok.loc.merge(&e.loc);
+    Ok(Ast::uniop(op, e, loc))
+}
+
+fn parse_group(parser: &mut Parser, open: Token) -> Result<Ast, ParseError> {
+    let e = parser.parse_expr(Precedence::Lowest)?;
+    match parser.tokens.next() {
+        Some(Token {
+            value: TokenKind::RParen,
+            ..
+        }) => Ok(e),
+        Some(t) => Err(ParseError::UnexpectedToken(t)),
+        None => Err(ParseError::UnclosedOpenParen(open)),
+    }
+}
+
+fn parse_binop(parser: &mut Parser, left: Ast, op_tok: Token) -> Result<Ast, ParseError> {
+    let prec = parser.infix_fns[&discriminant(&op_tok.value)].0;
+    let op = match op_tok.value {
+        TokenKind::Plus => BinOp::add(op_tok.loc.clone()),
+        TokenKind::Minus => BinOp::sub(op_tok.loc.clone()),
+        TokenKind::Asterisk => BinOp::mult(op_tok.loc.clone()),
+        TokenKind::Slash => BinOp::div(op_tok.loc.clone()),
+        TokenKind::Caret => BinOp::pow(op_tok.loc.clone()),
+        _ => unreachable!(),
+    };
+    // `^` is right-associative, so its right-hand side is parsed at one
+    // precedence level below its own; every other operator here is
+    // left-associative and parses its right-hand side at its own level.
+    let next_min = if matches!(op_tok.value, TokenKind::Caret) {
+        prec.lower()
+    } else {
+        prec
+    };
+    let right = parser.parse_expr(next_min)?;
+    let loc = left.loc.merge(&right.loc);
+    Ok(Ast::binop(op, left, right, loc))
+}
+
+/// Parses every statement in `tokens`. On a recoverable `ParseError` it
+/// records the error and keeps going from wherever the failing attempt left
+/// off, so a single call can surface every mistake in the input instead of
+/// just the first one.
+///
+/// There is no separate "skip to the next sync point" step: every
+/// `ParseError` is only ever raised after consuming the offending token (or,
+/// in the `Eof` case, after consuming everything there was), so the next
+/// loop iteration already resumes at a sensible spot. A prior version of
+/// this function skipped forward to the next `)` (or EOF) on every error,
+/// which silently discarded independent, subsequent error sites — e.g. `) )`
+/// reported only the first stray `)` and ate the second one, and
+/// `(1+) (2+)` reported only the first group's dangling `+` and discarded
+/// the whole second group.
+pub fn parse_all(tokens: Vec<Token>, eof_loc: Loc) -> Result<Vec<Statement>, Vec<Error>> {
+    let mut parser = Parser::new(tokens, eof_loc);
+    let mut statements = Vec::new();
+    let mut errors = Vec::new();
+
+    while parser.tokens.peek().is_some() {
+        match statement(&mut parser) {
+            Ok(s) => statements.push(s),
+            Err(e) => errors.push(Error::from(e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(statements)
+    } else {
+        Err(errors)
+    }
+}
+
+// STATEMENT := EXPR ("=" EXPR)?
+//
+// `EXPR "=" EXPR` is only a valid statement when the left-hand EXPR
+// collapsed to a bare identifier; anything else hitting a trailing `=`
+// (or a bare identifier hitting anything other than `=`) is reported via
+// `ExpectedIdent`/`ExpectedEquals` rather than the generic
+// `RedundantExpression`.
+fn statement(parser: &mut Parser) -> Result<Statement, ParseError> {
+    let expr = parser.parse_expr(Precedence::Lowest)?;
+    match (&expr.value, parser.tokens.peek().map(|tok| &tok.value)) {
+        (AstKind::Ident(name), Some(TokenKind::Equal)) => {
+            let name = name.clone();
+            parser.tokens.next();
+            let rhs = parser.parse_expr(Precedence::Lowest)?;
+            if parser.tokens.peek().is_some() {
+                return Err(ParseError::RedundantExpression(
+                    parser.tokens.next().unwrap(),
+                ));
+            }
+            let loc = expr.loc.merge(&rhs.loc);
+            Ok(Statement::assign(name, rhs, loc))
+        }
+        (AstKind::Ident(_), Some(_)) => {
+            Err(ParseError::ExpectedEquals(parser.tokens.next().unwrap()))
+        }
+        (_, Some(TokenKind::Equal)) => {
+            Err(ParseError::ExpectedIdent(parser.tokens.next().unwrap()))
+        }
+        (_, Some(_)) => Err(ParseError::RedundantExpression(
+            parser.tokens.next().unwrap(),
+        )),
+        (_, None) => Ok(Statement::expr(expr)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::lex;
+
+    fn parse(input: &str) -> Result<Vec<Statement>, Vec<Error>> {
+        let (tokens, eof_loc) = lex(input).unwrap();
+        parse_all(tokens, eof_loc)
+    }
+
+    #[test]
+    fn assignment_rejects_trailing_tokens() {
+        // `x = 1 2` must report the same `RedundantExpression` a bare
+        // `1 2` does, rather than silently splitting into two statements.
+        let errors = parse("x = 1 2").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            Error::Parser(ParseError::RedundantExpression(_))
+        ));
+    }
+
+    #[test]
+    fn parse_all_reports_independent_errors_separately() {
+        let errors = parse(") )").unwrap_err();
+        assert_eq!(errors.len(), 2);
+
+        let errors = parse("(1+) (2+)").unwrap_err();
+        assert_eq!(errors.len(), 2);
+
+        let errors = parse("1 2 3 4 5").unwrap_err();
+        assert_eq!(errors.len(), 2);
+    }
+
+    fn parse_expr_str(input: &str) -> Ast {
+        let statements = parse(input).unwrap();
+        match &statements[..] {
+            [Statement {
+                value: StatementKind::Expr(e),
+                ..
+            }] => e.clone(),
+            _ => panic!("expected a single expression statement"),
+        }
+    }
+
+    #[test]
+    fn caret_is_right_associative() {
+        // `2^3^2` must parse as `2^(3^2)`, not `(2^3)^2`.
+        let ast = parse_expr_str("2^3^2");
+        match ast.value {
+            AstKind::BinOp {
+                op:
+                    BinOp {
+                        value: BinOpKind::Pow,
+                        ..
+                    },
+                l,
+                r,
+            } => {
+                assert_eq!(l.value, AstKind::Num(2));
+                match r.value {
+                    AstKind::BinOp {
+                        op:
+                            BinOp {
+                                value: BinOpKind::Pow,
+                                ..
+                            },
+                        l,
+                        r,
+                    } => {
+                        assert_eq!(l.value, AstKind::Num(3));
+                        assert_eq!(r.value, AstKind::Num(2));
+                    }
+                    _ => panic!("expected the right-hand side to itself be a `^`"),
+                }
+            }
+            _ => panic!("expected a top-level `^`"),
+        }
+    }
+
+    #[test]
+    fn caret_binds_tighter_than_unary_minus() {
+        // `-2^2` must parse as `(-2)^2`, since unary operands parse at
+        // `Precedence::Prefix`, above `Power`.
+        let ast = parse_expr_str("-2^2");
+        match ast.value {
+            AstKind::BinOp {
+                op:
+                    BinOp {
+                        value: BinOpKind::Pow,
+                        ..
+                    },
+                l,
+                r,
+            } => {
+                assert!(matches!(
+                    l.value,
+                    AstKind::UniOp {
+                        op: UniOp {
+                            value: UniOpKind::Minus,
+                            ..
+                        },
+                        ..
+                    }
+                ));
+                assert_eq!(r.value, AstKind::Num(2));
+            }
+            _ => panic!("expected a top-level `^`"),
+        }
+    }
+}